@@ -0,0 +1,151 @@
+//! Construction helpers that source credentials and settings from the
+//! environment or a TOML config file, so keys aren't hard-coded into source.
+//!
+//! All three construction paths (builder, [`RemitanoApi::from_env`],
+//! [`RemitanoApi::from_config`]) converge on [`RemitanoApiBuilder`].
+
+use std::path::Path;
+use std::time::Duration;
+
+use serde::Deserialize;
+
+use crate::{RemitanoApi, RemitanoApiBuilder, RemitanoError, Result};
+
+/// The `[remitano]` table of a config file.
+#[derive(Debug, Deserialize)]
+struct RemitanoConfig {
+    key: String,
+    secret: String,
+    api_url: Option<String>,
+    timeout_ms: Option<u64>,
+    max_retries: Option<u32>,
+    base_backoff_ms: Option<u64>,
+    time_offset_ms: Option<i64>,
+    pool_idle_timeout_ms: Option<u64>,
+    proxy: Option<String>,
+}
+
+#[derive(Debug, Deserialize)]
+struct ConfigFile {
+    remitano: RemitanoConfig,
+}
+
+impl RemitanoApi {
+    /// Build from environment variables: `REMITANO_API_KEY` and
+    /// `REMITANO_API_SECRET` are required; `REMITANO_API_URL` and
+    /// `REMITANO_TIMEOUT_MS` are optional.
+    pub fn from_env() -> Result<Self> {
+        let mut builder = RemitanoApiBuilder::default();
+        builder.key(env_var("REMITANO_API_KEY")?);
+        builder.secret(env_var("REMITANO_API_SECRET")?);
+
+        if let Ok(api_url) = std::env::var("REMITANO_API_URL") {
+            builder.api_url(api_url);
+        }
+        if let Ok(timeout_ms) = std::env::var("REMITANO_TIMEOUT_MS") {
+            let timeout_ms = timeout_ms
+                .parse()
+                .map_err(|e| RemitanoError::Config(format!("REMITANO_TIMEOUT_MS: {e}")))?;
+            builder.timeout_ms(timeout_ms);
+        }
+
+        builder.build().map_err(|e| RemitanoError::Config(e.to_string()))
+    }
+
+    /// Build from a TOML config file containing a `[remitano]` table.
+    pub fn from_config(path: impl AsRef<Path>) -> Result<Self> {
+        let text =
+            std::fs::read_to_string(path).map_err(|e| RemitanoError::Config(e.to_string()))?;
+        let config: ConfigFile =
+            toml::from_str(&text).map_err(|e| RemitanoError::Config(e.to_string()))?;
+        let remitano = config.remitano;
+
+        let mut builder = RemitanoApiBuilder::default();
+        builder.key(remitano.key);
+        builder.secret(remitano.secret);
+
+        if let Some(api_url) = remitano.api_url {
+            builder.api_url(api_url);
+        }
+        if let Some(timeout_ms) = remitano.timeout_ms {
+            builder.timeout_ms(timeout_ms);
+        }
+        if let Some(max_retries) = remitano.max_retries {
+            builder.max_retries(max_retries);
+        }
+        if let Some(base_backoff_ms) = remitano.base_backoff_ms {
+            builder.base_backoff_ms(base_backoff_ms);
+        }
+        if let Some(time_offset_ms) = remitano.time_offset_ms {
+            builder.time_offset_ms(time_offset_ms);
+        }
+        if let Some(pool_idle_timeout_ms) = remitano.pool_idle_timeout_ms {
+            builder.pool_idle_timeout(Duration::from_millis(pool_idle_timeout_ms));
+        }
+        if let Some(proxy) = remitano.proxy {
+            builder.proxy(proxy);
+        }
+
+        builder.build().map_err(|e| RemitanoError::Config(e.to_string()))
+    }
+}
+
+/// Read a required environment variable, surfacing a [`RemitanoError::Config`]
+/// when it is absent.
+fn env_var(name: &str) -> Result<String> {
+    std::env::var(name).map_err(|_| RemitanoError::Config(format!("missing env var {name}")))
+}
+
+#[cfg(test)]
+mod tests {
+    use std::sync::atomic::Ordering;
+
+    use super::*;
+
+    #[test]
+    fn from_env_rejects_non_numeric_timeout() {
+        std::env::set_var("REMITANO_API_KEY", "key");
+        std::env::set_var("REMITANO_API_SECRET", "secret");
+        std::env::set_var("REMITANO_TIMEOUT_MS", "not-a-number");
+
+        let result = RemitanoApi::from_env();
+
+        std::env::remove_var("REMITANO_API_KEY");
+        std::env::remove_var("REMITANO_API_SECRET");
+        std::env::remove_var("REMITANO_TIMEOUT_MS");
+
+        assert!(matches!(result, Err(RemitanoError::Config(_))));
+    }
+
+    #[test]
+    fn from_config_maps_remitano_table() {
+        let toml = r#"
+            [remitano]
+            key = "my-key"
+            secret = "my-secret"
+            api_url = "https://example.test"
+            timeout_ms = 5000
+            max_retries = 7
+            base_backoff_ms = 250
+            time_offset_ms = -1500
+            pool_idle_timeout_ms = 9000
+            proxy = "http://proxy.test:8080"
+        "#;
+
+        let path = std::env::temp_dir().join("remitano_from_config_test.toml");
+        std::fs::write(&path, toml).unwrap();
+
+        let api = RemitanoApi::from_config(&path).unwrap();
+        std::fs::remove_file(&path).ok();
+
+        assert_eq!(api.key, "my-key");
+        assert_eq!(api.secret, "my-secret");
+        assert_eq!(api.api_url, "https://example.test");
+        assert_eq!(api.timeout_ms, 5000);
+        assert_eq!(api.max_retries, 7);
+        assert_eq!(api.base_backoff_ms, 250);
+        assert_eq!(api.time_offset.load(Ordering::Relaxed), -1500);
+        assert_eq!(api.pool_idle_timeout, Some(Duration::from_millis(9000)));
+        assert_eq!(api.proxy.as_deref(), Some("http://proxy.test:8080"));
+    }
+}
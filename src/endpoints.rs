@@ -0,0 +1,122 @@
+//! Typed wrappers over the most common Remitano endpoints.
+//!
+//! Each method builds the correct method/path/params and routes through the
+//! low-level [`RemitanoApi::request`], so the signing logic stays in one place
+//! and callers avoid hand-constructing `Map<String, Value>` query params.
+
+use serde::{Deserialize, Serialize};
+use serde_json::{Map, Value};
+
+use crate::{Method, RemitanoApi, RemitanoError, Result};
+
+/// A coin balance held by the authenticated account.
+#[derive(Debug, Clone, Deserialize)]
+pub struct CoinAccount {
+    pub id: String,
+    pub coin_currency: String,
+    pub balance: String,
+    #[serde(default)]
+    pub deposit_address: Option<String>,
+}
+
+/// An advertised buy/sell offer.
+#[derive(Debug, Clone, Deserialize)]
+pub struct Offer {
+    pub id: String,
+    pub offer_type: String,
+    pub coin_currency: String,
+    pub fiat_currency: String,
+    pub coin_amount: String,
+    pub price: String,
+    #[serde(default)]
+    pub status: Option<String>,
+}
+
+/// Parameters for creating a new offer.
+#[derive(Debug, Clone, Default, Serialize)]
+pub struct CreateOffer {
+    pub offer_type: String,
+    pub coin_currency: String,
+    pub fiat_currency: String,
+    pub coin_amount: String,
+    pub price: String,
+}
+
+/// A trade between two parties.
+#[derive(Debug, Clone, Deserialize)]
+pub struct Trade {
+    pub id: String,
+    pub coin_currency: String,
+    pub fiat_currency: String,
+    pub coin_amount: String,
+    pub status: String,
+}
+
+/// Optional filters for [`RemitanoApi::list_trades`].
+#[derive(Debug, Clone, Default, Serialize)]
+pub struct ListTrades {
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub coin_currency: Option<String>,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub fiat_currency: Option<String>,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub status: Option<String>,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub page: Option<u32>,
+}
+
+impl RemitanoApi {
+    /// List the authenticated account's coin balances.
+    pub async fn get_coin_accounts(&self) -> Result<Vec<CoinAccount>> {
+        self.request(Method::GET, "coin_accounts", None, None).await
+    }
+
+    /// Create a new offer.
+    pub async fn create_offer(&self, offer: &CreateOffer) -> Result<Offer> {
+        let body = serde_json::to_value(offer).map_err(RemitanoError::Deserialize)?;
+        self.request(Method::POST, "offers", None, Some(body)).await
+    }
+
+    /// Fetch a single offer by id.
+    pub async fn get_offer(&self, id: &str) -> Result<Offer> {
+        self.request(Method::GET, &format!("offers/{id}"), None, None)
+            .await
+    }
+
+    /// List trades, optionally filtered.
+    pub async fn list_trades(&self, filter: &ListTrades) -> Result<Vec<Trade>> {
+        self.request(Method::GET, "trades", params(filter)?, None)
+            .await
+    }
+}
+
+/// Serialize a filter struct into query params, dropping it entirely when no
+/// fields are set.
+fn params<T: Serialize>(filter: &T) -> Result<Option<Map<String, Value>>> {
+    let value = serde_json::to_value(filter).map_err(RemitanoError::Deserialize)?;
+    Ok(match value {
+        Value::Object(map) if !map.is_empty() => Some(map),
+        _ => None,
+    })
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn empty_filter_serializes_to_none() {
+        assert!(params(&ListTrades::default()).unwrap().is_none());
+    }
+
+    #[test]
+    fn set_field_serializes_to_some() {
+        let filter = ListTrades {
+            coin_currency: Some("BTC".to_string()),
+            ..ListTrades::default()
+        };
+        let map = params(&filter).unwrap().expect("expected params");
+        assert_eq!(map.get("coin_currency").and_then(|v| v.as_str()), Some("BTC"));
+        assert_eq!(map.len(), 1);
+    }
+}
@@ -0,0 +1,105 @@
+use std::fmt;
+
+use reqwest::StatusCode;
+use serde::Deserialize;
+use serde_json::{Map, Value};
+use thiserror::Error;
+
+/// Convenience alias for results produced by this crate.
+pub type Result<T> = std::result::Result<T, RemitanoError>;
+
+/// Errors that can occur while talking to the Remitano API.
+#[derive(Debug, Error)]
+pub enum RemitanoError {
+    /// The server answered with a non-2xx status. Carries the status code
+    /// together with the decoded Remitano error payload so callers can match
+    /// on rate-limit vs auth vs validation failures.
+    #[error("remitano responded with {status}: {body}")]
+    Http { status: StatusCode, body: ApiError },
+
+    /// A successful response body could not be decoded into the target type,
+    /// or a typed endpoint's request body or filter could not be encoded as
+    /// JSON.
+    #[error("failed to deserialize response: {0}")]
+    Deserialize(#[source] serde_json::Error),
+
+    /// The request could not be signed (bad secret length, serialization, ...).
+    #[error("failed to sign request: {0}")]
+    Signature(String),
+
+    /// The request did not complete within `timeout_ms`.
+    #[error("request timed out")]
+    Timeout,
+
+    /// Credentials or settings could not be loaded from the environment or a
+    /// config file.
+    #[error("configuration error: {0}")]
+    Config(String),
+
+    /// Any other transport-level failure from reqwest.
+    #[error(transparent)]
+    Request(#[from] reqwest::Error),
+}
+
+/// The JSON error shape returned by Remitano on a failed request.
+///
+/// Remitano is not entirely consistent about the envelope it uses, so every
+/// field is optional and anything unrecognised is preserved in `extra`.
+#[derive(Debug, Clone, Default, Deserialize)]
+pub struct ApiError {
+    #[serde(default)]
+    pub error: Option<String>,
+
+    #[serde(default)]
+    pub message: Option<String>,
+
+    #[serde(flatten)]
+    pub extra: Map<String, Value>,
+}
+
+impl fmt::Display for ApiError {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        if let Some(message) = &self.message {
+            f.write_str(message)
+        } else if let Some(error) = &self.error {
+            f.write_str(error)
+        } else {
+            write!(f, "{}", Value::Object(self.extra.clone()))
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn display_prefers_message() {
+        let err = ApiError {
+            message: Some("rate limited".to_string()),
+            error: Some("ignored".to_string()),
+            ..ApiError::default()
+        };
+        assert_eq!(err.to_string(), "rate limited");
+    }
+
+    #[test]
+    fn display_falls_back_to_error() {
+        let err = ApiError {
+            error: Some("invalid signature".to_string()),
+            ..ApiError::default()
+        };
+        assert_eq!(err.to_string(), "invalid signature");
+    }
+
+    #[test]
+    fn display_falls_back_to_extra() {
+        let mut extra = Map::new();
+        extra.insert("code".to_string(), Value::from(42));
+        let err = ApiError {
+            extra,
+            ..ApiError::default()
+        };
+        assert_eq!(err.to_string(), "{\"code\":42}");
+    }
+}
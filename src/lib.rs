@@ -1,19 +1,33 @@
+use std::sync::atomic::{AtomicI64, Ordering};
+use std::sync::Arc;
 use std::time::{Duration, SystemTime};
 
 use derive_builder::Builder;
 use hmac::{Hmac, Mac};
 use hyperx::header::HttpDate;
 use md5::{Digest, Md5};
-use reqwest::header::{HeaderMap, ACCEPT, AUTHORIZATION, CONTENT_TYPE, DATE, USER_AGENT};
+use rand::Rng;
+use reqwest::header::{
+    HeaderMap, InvalidHeaderValue, ACCEPT, AUTHORIZATION, CONTENT_TYPE, DATE, RETRY_AFTER,
+    USER_AGENT,
+};
 use serde::de::DeserializeOwned;
 use serde_json::{Map, Value};
 use sha1::Sha1;
 
+mod config;
+mod endpoints;
+mod error;
+
+pub use endpoints::*;
+pub use error::{ApiError, RemitanoError, Result};
+
 const API_URL: &str = "https://api.remitano.com";
 
 type HmacSha1 = Hmac<Sha1>;
 
 #[derive(Default, Builder, Debug)]
+#[builder(build_fn(name = "build_inner", private))]
 pub struct RemitanoApi {
     pub key: String,
 
@@ -24,32 +38,118 @@ pub struct RemitanoApi {
 
     #[builder(default = "3000")]
     pub timeout_ms: u64,
+
+    /// Maximum number of retries for transient failures (timeouts, connect
+    /// errors, HTTP 429/5xx). A value of `0` disables retrying.
+    #[builder(default = "3")]
+    pub max_retries: u32,
+
+    /// Base backoff in milliseconds; the delay grows as
+    /// `base_backoff_ms * 2^attempt` plus a random jitter.
+    #[builder(default = "500")]
+    pub base_backoff_ms: u64,
+
+    /// Offset in milliseconds added to the local clock before formatting the
+    /// `Date` header, used to compensate for clock drift. Seeded from the
+    /// builder and refreshed by [`RemitanoApi::sync_time`].
+    #[builder(setter(custom), default = "Arc::new(AtomicI64::new(0))")]
+    pub time_offset: Arc<AtomicI64>,
+
+    /// Idle connection timeout applied when the client is built from the
+    /// tuning knobs rather than supplied directly.
+    #[builder(setter(custom), default)]
+    pub pool_idle_timeout: Option<Duration>,
+
+    /// Proxy URL applied when the client is built from the tuning knobs.
+    #[builder(setter(custom), default)]
+    pub proxy: Option<String>,
+
+    /// Shared, connection-pooled HTTP client. Built once and reused for
+    /// every request instead of rebuilding the TLS stack each call.
+    #[builder(setter(custom), default = "reqwest::Client::new()")]
+    pub client: reqwest::Client,
+}
+
+impl RemitanoApiBuilder {
+    /// Supply a pre-configured [`reqwest::Client`]. Takes precedence over the
+    /// `pool_idle_timeout`/`proxy` knobs.
+    pub fn client(&mut self, client: reqwest::Client) -> &mut Self {
+        self.client = Some(client);
+        self
+    }
+
+    /// Seed the initial clock offset in milliseconds.
+    pub fn time_offset_ms(&mut self, offset: i64) -> &mut Self {
+        self.time_offset = Some(Arc::new(AtomicI64::new(offset)));
+        self
+    }
+
+    /// Tune how long idle connections are kept in the pool.
+    pub fn pool_idle_timeout(&mut self, timeout: Duration) -> &mut Self {
+        self.pool_idle_timeout = Some(Some(timeout));
+        self
+    }
+
+    /// Route requests through the given proxy URL.
+    pub fn proxy(&mut self, proxy: impl Into<String>) -> &mut Self {
+        self.proxy = Some(Some(proxy.into()));
+        self
+    }
+
+    /// Build the [`RemitanoApi`], assembling the HTTP client from the tuning
+    /// knobs unless one was supplied explicitly.
+    pub fn build(&mut self) -> std::result::Result<RemitanoApi, RemitanoApiBuilderError> {
+        // Assemble the client from the tuning knobs unless one was supplied,
+        // then hand it to `build_inner` so the field default (another
+        // `Client`) is never constructed on the common path.
+        if self.client.is_none() {
+            let mut builder = reqwest::Client::builder();
+            if let Some(Some(timeout)) = self.pool_idle_timeout {
+                builder = builder.pool_idle_timeout(timeout);
+            }
+            if let Some(Some(proxy)) = &self.proxy {
+                let proxy = reqwest::Proxy::all(proxy)
+                    .map_err(|e| RemitanoApiBuilderError::ValidationError(e.to_string()))?;
+                builder = builder.proxy(proxy);
+            }
+            self.client = Some(
+                builder
+                    .build()
+                    .map_err(|e| RemitanoApiBuilderError::ValidationError(e.to_string()))?,
+            );
+        }
+
+        self.build_inner()
+    }
 }
 
 pub use reqwest::Method;
 
 impl RemitanoApi {
-    fn hmac(&self, data: &Option<Value>) -> anyhow::Result<String> {
+    fn hmac(&self, data: &Option<Value>) -> Result<String> {
         let value = match data {
             Some(data) => match data {
                 Value::String(data) => data.as_bytes().to_vec(),
-                _ => serde_json::to_vec(&data)?,
+                _ => serde_json::to_vec(&data)
+                    .map_err(|e| RemitanoError::Signature(e.to_string()))?,
             },
             None => vec![],
         };
 
-        let mut mac = HmacSha1::new_from_slice(self.secret.as_bytes())?;
+        let mut mac = HmacSha1::new_from_slice(self.secret.as_bytes())
+            .map_err(|e| RemitanoError::Signature(e.to_string()))?;
         mac.update(&value);
         let result = mac.finalize().into_bytes();
 
         Ok(base64::encode(result))
     }
 
-    fn md5(&self, data: &Option<Value>) -> anyhow::Result<String> {
+    fn md5(&self, data: &Option<Value>) -> Result<String> {
         let value = match data {
             Some(data) => match data {
                 Value::String(data) => data.as_bytes().to_vec(),
-                _ => serde_json::to_vec(&data)?,
+                _ => serde_json::to_vec(&data)
+                    .map_err(|e| RemitanoError::Signature(e.to_string()))?,
             },
             None => vec![],
         };
@@ -61,26 +161,40 @@ impl RemitanoApi {
         Ok(base64::encode(result))
     }
 
-    pub async fn request<T: DeserializeOwned>(
+    /// Build a signed request for the given call. The signature is derived
+    /// from the current `Date` header, so this must be called afresh for each
+    /// attempt.
+    fn signed_request(
         &self,
-        method: Method,
+        method: &Method,
         endpoint: &str,
-        params: Option<Map<String, Value>>,
-        body: Option<Value>,
-    ) -> anyhow::Result<T> {
+        params: &Option<Map<String, Value>>,
+        body: &Option<Value>,
+    ) -> Result<reqwest::RequestBuilder> {
+        let sig = |v: InvalidHeaderValue| RemitanoError::Signature(v.to_string());
+
         let mut headers = HeaderMap::new();
         headers.insert(
             USER_AGENT,
             "Mozilla/5.0 (Macintosh; Intel Mac OS X 10.15; rv:85.0) Gecko/20100101 Firefox/85.0"
-                .parse()?,
+                .parse()
+                .map_err(sig)?,
+        );
+        headers.insert(ACCEPT, "application/json".parse().map_err(sig)?);
+        headers.insert(CONTENT_TYPE, "application/json".parse().map_err(sig)?);
+        headers.insert("Content-MD5", self.md5(body)?.parse().map_err(sig)?);
+        let now = with_offset(SystemTime::now(), self.time_offset.load(Ordering::Relaxed));
+        headers.insert(
+            DATE,
+            HttpDate::from(now).to_string().parse().map_err(sig)?,
         );
-        headers.insert(ACCEPT, "application/json".parse()?);
-        headers.insert(CONTENT_TYPE, "application/json".parse()?);
-        headers.insert("Content-MD5", self.md5(&body)?.parse()?);
-        headers.insert(DATE, HttpDate::from(SystemTime::now()).to_string().parse()?);
 
-        let query = if let Some(params) = &params {
-            format!("?{}", &serde_qs::to_string(&params)?)
+        let query = if let Some(params) = params {
+            format!(
+                "?{}",
+                &serde_qs::to_string(params)
+                    .map_err(|e| RemitanoError::Signature(e.to_string()))?
+            )
         } else {
             "".to_string()
         };
@@ -88,7 +202,7 @@ impl RemitanoApi {
         let request_url = format!("api/v1/{}{}", &endpoint, &query);
         let request_str = format!(
             "{},application/json,{},/{},{}",
-            &method,
+            method,
             headers
                 .get("Content-MD5")
                 .map_or_else(|| Some(""), |v| v.to_str().ok())
@@ -99,24 +213,189 @@ impl RemitanoApi {
                 .map_or_else(|| Some(""), |v| v.to_str().ok())
                 .unwrap(),
         );
-        let sig = self.hmac(&Some(Value::String(request_str)))?;
+        let signature = self.hmac(&Some(Value::String(request_str)))?;
         headers.insert(
             AUTHORIZATION,
-            format!("APIAuth {}:{}", &self.key, &sig).parse()?,
+            format!("APIAuth {}:{}", &self.key, &signature)
+                .parse()
+                .map_err(sig)?,
         );
 
-        let client = reqwest::Client::new();
-        let resp: T = client
-            .request(method, format!("{}/{}", &self.api_url, &request_url))
+        let builder = self
+            .client
+            .request(method.clone(), format!("{}/{}", &self.api_url, &request_url))
             .headers(headers)
-            .json(&body.unwrap_or_default())
+            .json(&body.clone().unwrap_or_default())
+            .timeout(Duration::from_millis(self.timeout_ms));
+
+        Ok(builder)
+    }
+
+    pub async fn request<T: DeserializeOwned>(
+        &self,
+        method: Method,
+        endpoint: &str,
+        params: Option<Map<String, Value>>,
+        body: Option<Value>,
+    ) -> Result<T> {
+        let mut attempt: u32 = 0;
+
+        loop {
+            // Re-sign on every attempt: the `Date` header and therefore the
+            // HMAC change between sends.
+            let builder = self.signed_request(&method, endpoint, &params, &body)?;
+
+            match Self::send_and_parse::<T>(builder).await {
+                Ok(value) => return Ok(value),
+                Err(Attempt { error, retry_after }) => {
+                    if attempt >= self.max_retries || !is_retryable(&error) {
+                        return Err(error);
+                    }
+
+                    let delay = retry_after.unwrap_or_else(|| {
+                        let jitter = rand::thread_rng().gen_range(0..self.base_backoff_ms.max(1));
+                        backoff_delay(self.base_backoff_ms, attempt, jitter)
+                    });
+
+                    tokio::time::sleep(delay).await;
+                    attempt += 1;
+                }
+            }
+        }
+    }
+
+    /// Synchronize against the server clock by reading the `Date` header of a
+    /// lightweight call, storing the delta as the offset applied to future
+    /// requests. Returns the new offset in milliseconds.
+    pub async fn sync_time(&self) -> Result<i64> {
+        let resp = self
+            .client
+            .get(&self.api_url)
             .timeout(Duration::from_millis(self.timeout_ms))
             .send()
-            .await?
-            .json()
-            .await?;
+            .await
+            .map_err(|e| {
+                if e.is_timeout() {
+                    RemitanoError::Timeout
+                } else {
+                    RemitanoError::Request(e)
+                }
+            })?;
+
+        let server_time: SystemTime = resp
+            .headers()
+            .get(DATE)
+            .and_then(|v| v.to_str().ok())
+            .and_then(|s| s.parse::<HttpDate>().ok())
+            .map(SystemTime::from)
+            .ok_or_else(|| RemitanoError::Signature("server returned no Date header".into()))?;
+
+        let local = SystemTime::now();
+        let offset_ms = match server_time.duration_since(local) {
+            Ok(d) => d.as_millis() as i64,
+            Err(e) => -(e.duration().as_millis() as i64),
+        };
 
-        Ok(resp)
+        self.time_offset.store(offset_ms, Ordering::Relaxed);
+
+        Ok(offset_ms)
+    }
+
+    /// Send a prepared request and decode the response, capturing any
+    /// `Retry-After` hint so the caller can honour it.
+    async fn send_and_parse<T: DeserializeOwned>(
+        builder: reqwest::RequestBuilder,
+    ) -> std::result::Result<T, Attempt> {
+        let resp = match builder.send().await {
+            Ok(resp) => resp,
+            Err(e) => {
+                let error = if e.is_timeout() {
+                    RemitanoError::Timeout
+                } else {
+                    RemitanoError::Request(e)
+                };
+                return Err(Attempt::new(error, None));
+            }
+        };
+
+        let status = resp.status();
+        let retry_after = resp
+            .headers()
+            .get(RETRY_AFTER)
+            .and_then(|v| v.to_str().ok())
+            .and_then(parse_retry_after);
+
+        let text = match resp.text().await {
+            Ok(text) => text,
+            Err(e) => return Err(Attempt::new(RemitanoError::Request(e), retry_after)),
+        };
+
+        if !status.is_success() {
+            let body = serde_json::from_str::<ApiError>(&text).unwrap_or_else(|_| ApiError {
+                message: Some(text.clone()),
+                ..ApiError::default()
+            });
+            return Err(Attempt::new(RemitanoError::Http { status, body }, retry_after));
+        }
+
+        serde_json::from_str(&text)
+            .map_err(|e| Attempt::new(RemitanoError::Deserialize(e), None))
+    }
+}
+
+/// Result of a single send attempt that failed, with an optional server-sent
+/// `Retry-After` delay.
+struct Attempt {
+    error: RemitanoError,
+    retry_after: Option<Duration>,
+}
+
+impl Attempt {
+    fn new(error: RemitanoError, retry_after: Option<Duration>) -> Self {
+        Self { error, retry_after }
+    }
+}
+
+/// Exponential backoff delay for a given attempt: `base_backoff_ms * 2^attempt`
+/// plus the supplied jitter, saturating instead of overflowing.
+fn backoff_delay(base_backoff_ms: u64, attempt: u32, jitter_ms: u64) -> Duration {
+    let exp = base_backoff_ms.saturating_mul(1u64.checked_shl(attempt).unwrap_or(u64::MAX));
+    Duration::from_millis(exp.saturating_add(jitter_ms))
+}
+
+/// Parse a `Retry-After` header value, honouring both the delta-seconds form
+/// (`120`) and the HTTP-date form (`Wed, 21 Oct 2015 07:28:00 GMT`).
+fn parse_retry_after(value: &str) -> Option<Duration> {
+    let value = value.trim();
+    if let Ok(secs) = value.parse::<u64>() {
+        return Some(Duration::from_secs(secs));
+    }
+    let when = SystemTime::from(value.parse::<HttpDate>().ok()?);
+    when.duration_since(SystemTime::now()).ok()
+}
+
+/// Shift a timestamp by a signed millisecond offset, saturating at the epoch.
+fn with_offset(now: SystemTime, offset_ms: i64) -> SystemTime {
+    let delta = Duration::from_millis(offset_ms.unsigned_abs());
+    if offset_ms >= 0 {
+        now.checked_add(delta).unwrap_or(now)
+    } else {
+        now.checked_sub(delta)
+            .filter(|t| *t >= SystemTime::UNIX_EPOCH)
+            .unwrap_or(SystemTime::UNIX_EPOCH)
+    }
+}
+
+/// Whether an error is worth retrying: timeouts, connect errors, and HTTP
+/// 429/5xx. Other 4xx and deserialize failures bubble up immediately.
+fn is_retryable(error: &RemitanoError) -> bool {
+    match error {
+        RemitanoError::Timeout => true,
+        RemitanoError::Request(e) => e.is_timeout() || e.is_connect(),
+        RemitanoError::Http { status, .. } => {
+            status.as_u16() == 429 || status.is_server_error()
+        }
+        _ => false,
     }
 }
 
@@ -151,4 +430,75 @@ mod tests {
         let result = remitano_api.hmac(&Some(json!(input))).unwrap();
         assert_eq!("oSVlCBpf9BqviWbUjOm4DXEcgRo=", result);
     }
+
+    #[test]
+    fn test_backoff_delay_sequence() {
+        // base * 2^attempt with zero jitter.
+        assert_eq!(backoff_delay(500, 0, 0), Duration::from_millis(500));
+        assert_eq!(backoff_delay(500, 1, 0), Duration::from_millis(1000));
+        assert_eq!(backoff_delay(500, 2, 0), Duration::from_millis(2000));
+        assert_eq!(backoff_delay(500, 3, 0), Duration::from_millis(4000));
+        // Jitter is added on top of the exponential term.
+        assert_eq!(backoff_delay(500, 1, 123), Duration::from_millis(1123));
+        // A wildly large attempt saturates instead of overflowing.
+        assert_eq!(backoff_delay(500, 64, 0), Duration::from_millis(u64::MAX));
+    }
+
+    #[test]
+    fn test_backoff_jitter_in_range() {
+        let base = 500;
+        for attempt in 0..4 {
+            let jitter = rand::thread_rng().gen_range(0..base);
+            assert!(jitter < base);
+            let delay = backoff_delay(base, attempt, jitter).as_millis() as u64;
+            let exp = base * (1 << attempt);
+            assert!(delay >= exp && delay < exp + base);
+        }
+    }
+
+    #[test]
+    fn test_is_retryable() {
+        assert!(is_retryable(&RemitanoError::Timeout));
+        assert!(is_retryable(&RemitanoError::Http {
+            status: reqwest::StatusCode::TOO_MANY_REQUESTS,
+            body: ApiError::default(),
+        }));
+        assert!(is_retryable(&RemitanoError::Http {
+            status: reqwest::StatusCode::INTERNAL_SERVER_ERROR,
+            body: ApiError::default(),
+        }));
+        assert!(!is_retryable(&RemitanoError::Http {
+            status: reqwest::StatusCode::BAD_REQUEST,
+            body: ApiError::default(),
+        }));
+        let serde_err = serde_json::from_str::<u8>("\"x\"").unwrap_err();
+        assert!(!is_retryable(&RemitanoError::Deserialize(serde_err)));
+    }
+
+    #[test]
+    fn test_parse_retry_after() {
+        assert_eq!(parse_retry_after("120"), Some(Duration::from_secs(120)));
+        // An HTTP-date in the past yields `None`, so the caller falls back to
+        // the computed backoff.
+        assert_eq!(
+            parse_retry_after("Wed, 21 Oct 2015 07:28:00 GMT"),
+            None,
+        );
+    }
+
+    #[test]
+    fn test_with_offset_direction() {
+        let base = SystemTime::UNIX_EPOCH + Duration::from_secs(1_000);
+        assert_eq!(with_offset(base, 1000), base + Duration::from_millis(1000));
+        assert_eq!(with_offset(base, -1000), base - Duration::from_millis(1000));
+    }
+
+    #[test]
+    fn test_with_offset_saturates_at_epoch() {
+        // A negative offset below the epoch saturates rather than panicking.
+        assert_eq!(
+            with_offset(SystemTime::UNIX_EPOCH, -1000),
+            SystemTime::UNIX_EPOCH
+        );
+    }
 }